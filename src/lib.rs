@@ -1,23 +1,111 @@
 #![no_std]
 
+use core::cell::UnsafeCell;
 use core::convert::Infallible;
 use core::mem::MaybeUninit;
-use cortex_m::interrupt::free as interrupt_free;
+use core::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
 
-const BUF_SIZE: usize = 16;
-pub struct RingBuf<T> {
-    front: usize,
-    back: usize,
-    data: [MaybeUninit<T>; BUF_SIZE],
+const fn is_power_of_two(n: usize) -> bool {
+    n != 0 && (n & (n - 1)) == 0
 }
 
-impl<T: Copy> RingBuf<T> {
-    pub fn new() -> Self {
-        interrupt_free(|_| Self {
-            front: usize::default(),
-            back: usize::default(),
-            data: [MaybeUninit::uninit(); BUF_SIZE],
-        })
+/// A 64-bit counter built from two `AtomicU32` halves, since plain
+/// `AtomicU64` isn't available on mainline Cortex-M targets (Armv6-M through
+/// Armv8-M all lack `target_has_atomic = "64"`).
+///
+/// Only [`incr`](Self::incr)/[`decr`](Self::decr) from a single owner at a
+/// time. [`get`](Self::get) retries if `hi` changes between its two loads of
+/// it, which catches a carry/borrow that lands while it is reading; a read that
+/// runs entirely between the `hi` and `lo` stores of a carrying/borrowing
+/// update is not detected.
+struct SeqCounter {
+    hi: AtomicU32,
+    lo: AtomicU32,
+}
+
+impl SeqCounter {
+    const fn new() -> Self {
+        Self {
+            hi: AtomicU32::new(0),
+            lo: AtomicU32::new(0),
+        }
+    }
+
+    fn incr(&self) {
+        let lo = self.lo.load(Ordering::Relaxed).wrapping_add(1);
+        if lo == 0 {
+            self.hi.fetch_add(1, Ordering::Relaxed);
+        }
+        self.lo.store(lo, Ordering::Relaxed);
+    }
+
+    fn decr(&self) {
+        let prev_lo = self.lo.load(Ordering::Relaxed);
+        if prev_lo == 0 {
+            self.hi.fetch_sub(1, Ordering::Relaxed);
+        }
+        self.lo.store(prev_lo.wrapping_sub(1), Ordering::Relaxed);
+    }
+
+    fn get(&self) -> u64 {
+        loop {
+            let hi1 = self.hi.load(Ordering::Relaxed);
+            let lo = self.lo.load(Ordering::Relaxed);
+            let hi2 = self.hi.load(Ordering::Relaxed);
+            if hi1 == hi2 {
+                return ((hi1 as u64) << 32) | lo as u64;
+            }
+        }
+    }
+}
+
+pub struct RingBuf<T, const N: usize> {
+    front: AtomicUsize,
+    back: AtomicUsize,
+    data: UnsafeCell<[MaybeUninit<T>; N]>,
+    /// 64-bit mirrors of `front`/`back`, so sequence numbers don't wrap at
+    /// `usize::MAX` on 32-bit targets. `push_front` on a buffer whose oldest
+    /// sequence number is `0` wraps `seq_front` to `u64::MAX`.
+    ///
+    /// [`Producer`]/[`Consumer`] keep them in sync through a shared reference
+    /// after [`RingBuf::split`].
+    seq_front: SeqCounter,
+    seq_back: SeqCounter,
+}
+
+impl<T, const N: usize> RingBuf<T, N> {
+    const IS_POW2: bool = is_power_of_two(N);
+    const ASSERT_NONZERO_CAPACITY: () = assert!(N > 0, "RingBuf capacity N must not be zero");
+
+    pub const fn new() -> Self {
+        let () = Self::ASSERT_NONZERO_CAPACITY;
+        Self {
+            front: AtomicUsize::new(0),
+            back: AtomicUsize::new(0),
+            // SAFETY: an all-uninitialized `[MaybeUninit<T>; N]` is itself a valid
+            // value of `MaybeUninit<[MaybeUninit<T>; N]>`, for any `T`.
+            data: UnsafeCell::new(unsafe { MaybeUninit::uninit().assume_init() }),
+            seq_front: SeqCounter::new(),
+            seq_back: SeqCounter::new(),
+        }
+    }
+
+    /// Maps a monotonically increasing logical index onto a physical slot in `data`.
+    ///
+    /// When `N` is a power of two this is a single `AND`; otherwise it falls back to `%`.
+    #[inline]
+    fn wrap_index(index: usize) -> usize {
+        if Self::IS_POW2 {
+            index & (N - 1)
+        } else {
+            index % N
+        }
+    }
+
+    /// Raw pointer to the physical slot `phys`. Callers must ensure exclusive access to it.
+    #[inline]
+    unsafe fn slot(&self, phys: usize) -> *mut MaybeUninit<T> {
+        (self.data.get() as *mut MaybeUninit<T>).add(phys)
     }
 
     pub fn is_full(&self) -> bool {
@@ -29,7 +117,7 @@ impl<T: Copy> RingBuf<T> {
     }
 
     pub fn capacity(&self) -> usize {
-        BUF_SIZE
+        N
     }
 
     pub fn free(&self) -> usize {
@@ -37,123 +125,627 @@ impl<T: Copy> RingBuf<T> {
     }
 
     pub fn len(&self) -> usize {
-        if self.front <= self.back {
-            self.back - self.front
-        } else {
-            self.capacity() - self.front + self.back
-        }
+        let back = self.back.load(Ordering::Relaxed);
+        let front = self.front.load(Ordering::Relaxed);
+        back.wrapping_sub(front)
     }
 
-    pub fn get(&self, i: usize) -> nb::Result<T, Infallible> {
+    pub fn get(&self, i: usize) -> Option<&T> {
         if self.len() <= i {
-            return Err(nb::Error::WouldBlock);
+            return None;
         }
 
-        Ok(unsafe { self.data[self.front + i].assume_init() })
+        let front = self.front.load(Ordering::Relaxed);
+        let slot = unsafe { self.slot(Self::wrap_index(front.wrapping_add(i))) };
+        Some(unsafe { &*(slot as *const T) })
     }
 
     pub fn push_back(&mut self, item: T) -> nb::Result<(), Infallible> {
         if self.is_full() {
             return Err(nb::Error::WouldBlock);
         }
-        unsafe { self.data[self.back].as_mut_ptr().write(item) };
-        if self.back == self.capacity() - 1 {
-            self.back = 0;
-        } else {
-            self.back += 1;
-        }
+        let back = self.back.load(Ordering::Relaxed);
+        let slot = Self::wrap_index(back);
+        unsafe { (*self.slot(slot)).write(item) };
+        self.back.store(back.wrapping_add(1), Ordering::Relaxed);
+        self.seq_back.incr();
         Ok(())
     }
 
+    /// Like [`push_back`](Self::push_back), but never blocks: if the buffer is full,
+    /// the oldest element (at `front`) is dropped to make room.
+    ///
+    /// Use this for lossy "keep the most recent `N` samples" streams, e.g. logging
+    /// raw ADC readings, where losing stale data is fine and backpressure is not.
+    /// Use `push_back` instead when a caller must know it missed a write.
+    pub fn push_back_overwriting(&mut self, item: T) {
+        if self.is_full() {
+            let front = self.front.load(Ordering::Relaxed);
+            unsafe { core::ptr::drop_in_place((*self.slot(Self::wrap_index(front))).as_mut_ptr()) };
+            self.front.store(front.wrapping_add(1), Ordering::Relaxed);
+            self.seq_front.incr();
+        }
+        let back = self.back.load(Ordering::Relaxed);
+        let slot = Self::wrap_index(back);
+        unsafe { (*self.slot(slot)).write(item) };
+        self.back.store(back.wrapping_add(1), Ordering::Relaxed);
+        self.seq_back.incr();
+    }
+
     pub fn push_front(&mut self, item: T) -> nb::Result<(), Infallible> {
         if self.is_full() {
             return Err(nb::Error::WouldBlock);
         }
-        if self.front == 0 {
-            self.front = self.capacity() - 1;
-        } else {
-            self.front -= 1;
-        }
-        unsafe { self.data[self.front].as_mut_ptr().write(item) };
+        let front = self.front.load(Ordering::Relaxed).wrapping_sub(1);
+        let slot = Self::wrap_index(front);
+        unsafe { (*self.slot(slot)).write(item) };
+        self.front.store(front, Ordering::Relaxed);
+        self.seq_front.decr();
         Ok(())
     }
 
+    /// Like [`push_front`](Self::push_front), but never blocks: if the buffer is
+    /// full, the newest element (at `back`) is dropped to make room.
+    ///
+    /// See [`push_back_overwriting`](Self::push_back_overwriting) for when to
+    /// prefer the lossy variant over the blocking one.
+    pub fn push_front_overwriting(&mut self, item: T) {
+        if self.is_full() {
+            let back = self.back.load(Ordering::Relaxed).wrapping_sub(1);
+            unsafe { core::ptr::drop_in_place((*self.slot(Self::wrap_index(back))).as_mut_ptr()) };
+            self.back.store(back, Ordering::Relaxed);
+            self.seq_back.decr();
+        }
+        let front = self.front.load(Ordering::Relaxed).wrapping_sub(1);
+        let slot = Self::wrap_index(front);
+        unsafe { (*self.slot(slot)).write(item) };
+        self.front.store(front, Ordering::Relaxed);
+        self.seq_front.decr();
+    }
+
     pub fn pop_back(&mut self) -> nb::Result<T, Infallible> {
         if self.is_empty() {
             return Err(nb::Error::WouldBlock);
         }
 
-        if self.back == 0 {
-            self.back = self.capacity() - 1;
-        } else {
-            self.back -= 1;
-        }
-        let d = self.data[self.back];
+        let back = self.back.load(Ordering::Relaxed).wrapping_sub(1);
+        let d = unsafe { (*self.slot(Self::wrap_index(back))).assume_init_read() };
+        self.back.store(back, Ordering::Relaxed);
+        self.seq_back.decr();
 
-        Ok(unsafe { d.assume_init() })
+        Ok(d)
     }
 
     pub fn pop_front(&mut self) -> nb::Result<T, Infallible> {
         if self.is_empty() {
             return Err(nb::Error::WouldBlock);
         }
-        let d = self.data[self.front];
-        if self.front == self.capacity() {
-            self.front = 0;
+        let front = self.front.load(Ordering::Relaxed);
+        let d = unsafe { (*self.slot(Self::wrap_index(front))).assume_init_read() };
+        self.front.store(front.wrapping_add(1), Ordering::Relaxed);
+        self.seq_front.incr();
+        Ok(d)
+    }
+
+    /// Returns the absolute sequence number of the oldest retained element, or
+    /// `None` if the buffer is empty.
+    pub fn oldest_seq(&self) -> Option<u64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.seq_front.get())
+        }
+    }
+
+    /// Returns the absolute sequence number of the newest retained element, or
+    /// `None` if the buffer is empty.
+    pub fn newest_seq(&self) -> Option<u64> {
+        if self.is_empty() {
+            None
         } else {
-            self.front += 1;
+            Some(self.seq_back.get().wrapping_sub(1))
+        }
+    }
+
+    /// Looks up an element by its absolute, never-reset push sequence number
+    /// rather than an offset relative to the current `front`. Returns `None` if
+    /// `seq` is below [`oldest_seq`](Self::oldest_seq) (already evicted) or at or
+    /// beyond [`newest_seq`](Self::newest_seq) + 1 (not yet written).
+    pub fn get_abs(&self, seq: u64) -> Option<&T> {
+        let seq_front = self.seq_front.get();
+        let seq_back = self.seq_back.get();
+        let offset = seq.wrapping_sub(seq_front);
+        if offset >= seq_back.wrapping_sub(seq_front) {
+            return None;
+        }
+        self.get(offset as usize)
+    }
+
+    /// Returns the buffer's contents as two physical slices in logical order:
+    /// `(first, second)` where `first` starts at the current `front`. `second`
+    /// is empty unless the logical contents wrap past the end of the physical
+    /// array, in which case it holds the remainder, mirroring `VecDeque`'s
+    /// `as_slices`.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let front = self.front.load(Ordering::Relaxed);
+        let len = self.len();
+        let start = Self::wrap_index(front);
+        let first_len = core::cmp::min(len, N - start);
+        let second_len = len - first_len;
+        let first =
+            unsafe { core::slice::from_raw_parts(self.slot(start) as *const T, first_len) };
+        let second =
+            unsafe { core::slice::from_raw_parts(self.slot(0) as *const T, second_len) };
+        (first, second)
+    }
+
+    /// Mutable counterpart of [`as_slices`](Self::as_slices).
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let front = self.front.load(Ordering::Relaxed);
+        let len = self.len();
+        let start = Self::wrap_index(front);
+        let first_len = core::cmp::min(len, N - start);
+        let second_len = len - first_len;
+        let first =
+            unsafe { core::slice::from_raw_parts_mut(self.slot(start) as *mut T, first_len) };
+        let second =
+            unsafe { core::slice::from_raw_parts_mut(self.slot(0) as *mut T, second_len) };
+        (first, second)
+    }
+
+    /// Rotates the physical storage in place so the logical contents occupy a
+    /// single contiguous range starting at physical slot `0`, and returns that
+    /// range as one slice. Prefer [`as_slices`](Self::as_slices) if you don't
+    /// need a single contiguous slice, since that never needs to move data.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        let front = self.front.load(Ordering::Relaxed);
+        let len = self.len();
+        let start = Self::wrap_index(front);
+        if start != 0 {
+            let data = unsafe { &mut *self.data.get() };
+            data.rotate_left(start);
+            self.front.store(0, Ordering::Relaxed);
+            self.back.store(len, Ordering::Relaxed);
         }
-        Ok(unsafe { d.assume_init() })
+        unsafe { core::slice::from_raw_parts_mut(self.data.get() as *mut T, len) }
+    }
+
+    /// Returns a borrowing iterator over the elements, from `front` to `back`.
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter { buf: self, idx: 0 }
+    }
+
+    /// Pushes items from `iter` onto the back until either `iter` is exhausted
+    /// or the buffer is full, and returns how many were accepted.
+    pub fn push_from_iter<I: IntoIterator<Item = T>>(&mut self, iter: I) -> usize {
+        let mut accepted = 0;
+        for item in iter {
+            if self.push_back(item).is_err() {
+                break;
+            }
+            accepted += 1;
+        }
+        accepted
+    }
+
+    /// Splits the buffer into a single-producer/single-consumer pair.
+    ///
+    /// The producer only ever advances `back` and the consumer only ever advances
+    /// `front`, so the two halves can be handed to a main loop and an interrupt
+    /// handler respectively without disabling interrupts on every access: the
+    /// `Release`/`Acquire` pair on `back` makes a written slot visible to the
+    /// consumer before it observes the new `back` value.
+    ///
+    /// Requires a `'static` buffer (e.g. a `static mut RingBuf`) since `Producer`
+    /// and `Consumer` each keep a shared reference to it for as long as they live.
+    ///
+    /// Only `Producer::push`/`Consumer::pull` are safe to call concurrently with
+    /// each other. `RingBuf`'s own `&self` methods (`get`, `get_abs`, `as_slices`,
+    /// `iter`, `oldest_seq`, `newest_seq`, ...) use `Relaxed` loads of `front`/
+    /// `back` and aren't synchronized against a concurrently-running `Producer`
+    /// or `Consumer` on the other side: call them only from whichever context
+    /// currently owns the `Producer` or the `Consumer`, never from a third,
+    /// independent holder of this `RingBuf` while a `Producer`/`Consumer` pair
+    /// is active.
+    pub fn split(&'static mut self) -> (Producer<'static, T, N>, Consumer<'static, T, N>) {
+        let shared: &'static Self = self;
+        (Producer { inner: shared }, Consumer { inner: shared })
     }
 }
 
-impl<T: Copy> core::ops::Index<usize> for RingBuf<T> {
-    type Output = T;
-    fn index(&self, i: usize) -> &Self::Output {
-        if self.len() <= i {
-            panic!("Out of bounds")
+impl<T, const N: usize> Default for RingBuf<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drops exactly the `len()` initialized slots between `front` and `back`,
+/// respecting wraparound, so evicted or never-popped elements aren't leaked.
+impl<T, const N: usize> Drop for RingBuf<T, N> {
+    fn drop(&mut self) {
+        let (first, second) = self.as_mut_slices();
+        unsafe {
+            core::ptr::drop_in_place(first);
+            core::ptr::drop_in_place(second);
         }
+    }
+}
+
+/// The producing half of a [`RingBuf`] obtained via [`RingBuf::split`].
+///
+/// Only advances `back`; safe to use from an interrupt handler while a
+/// [`Consumer`] is used elsewhere.
+pub struct Producer<'a, T, const N: usize> {
+    inner: &'a RingBuf<T, N>,
+}
+
+/// The consuming half of a [`RingBuf`] obtained via [`RingBuf::split`].
+///
+/// Only advances `front`; safe to use from an interrupt handler while a
+/// [`Producer`] is used elsewhere.
+pub struct Consumer<'a, T, const N: usize> {
+    inner: &'a RingBuf<T, N>,
+}
 
-        let d = unsafe { self.data[self.front + i].assume_init() };
-        unsafe { &*((&d) as *const T) }
+unsafe impl<'a, T: Send, const N: usize> Send for Producer<'a, T, N> {}
+unsafe impl<'a, T: Send, const N: usize> Send for Consumer<'a, T, N> {}
+
+impl<'a, T, const N: usize> Producer<'a, T, N> {
+    pub fn push(&mut self, item: T) -> nb::Result<(), Infallible> {
+        let back = self.inner.back.load(Ordering::Relaxed);
+        let front = self.inner.front.load(Ordering::Acquire);
+        if back.wrapping_sub(front) == N {
+            return Err(nb::Error::WouldBlock);
+        }
+        let slot = RingBuf::<T, N>::wrap_index(back);
+        unsafe { (*self.inner.slot(slot)).write(item) };
+        self.inner.seq_back.incr();
+        self.inner.back.store(back.wrapping_add(1), Ordering::Release);
+        Ok(())
     }
 }
 
-impl<T: Copy> core::ops::IndexMut<usize> for RingBuf<T> {
+impl<'a, T, const N: usize> Consumer<'a, T, N> {
+    pub fn pull(&mut self) -> nb::Result<T, Infallible> {
+        let front = self.inner.front.load(Ordering::Relaxed);
+        let back = self.inner.back.load(Ordering::Acquire);
+        if back.wrapping_sub(front) == 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+        let slot = RingBuf::<T, N>::wrap_index(front);
+        let item = unsafe { (*self.inner.slot(slot)).assume_init_read() };
+        self.inner.seq_front.incr();
+        self.inner.front.store(front.wrapping_add(1), Ordering::Release);
+        Ok(item)
+    }
+}
+
+impl<T, const N: usize> core::ops::Index<usize> for RingBuf<T, N> {
+    type Output = T;
+    fn index(&self, i: usize) -> &Self::Output {
+        self.get(i).expect("Out of bounds")
+    }
+}
+
+impl<T, const N: usize> core::ops::IndexMut<usize> for RingBuf<T, N> {
     fn index_mut(&mut self, i: usize) -> &mut Self::Output {
         if self.len() <= i {
             panic!("Out of bounds")
         }
 
-        let mut d = unsafe { self.data[self.front + i].assume_init() };
-        unsafe { &mut *(&mut d as *mut T) }
+        let front = self.front.load(Ordering::Relaxed);
+        let slot = unsafe { self.slot(Self::wrap_index(front.wrapping_add(i))) };
+        unsafe { &mut *(slot as *mut T) }
     }
 }
 
-impl<T: Copy> core::ops::Index<core::ops::Range<usize>> for RingBuf<T> {
+impl<T, const N: usize> core::ops::Index<core::ops::Range<usize>> for RingBuf<T, N> {
     type Output = [T];
 
+    /// Only covers the common case where the range doesn't cross the physical
+    /// wrap point; a logically contiguous range can be physically split, and a
+    /// split range cannot be represented as a single `&[T]`. Use
+    /// [`as_slices`](Self::as_slices) or [`make_contiguous`](Self::make_contiguous)
+    /// when the range might wrap.
     fn index(&self, i: core::ops::Range<usize>) -> &Self::Output {
         if self.len() < i.end {
             panic!("Out of bounds")
         }
-        let d = core::ptr::slice_from_raw_parts(
-            &self.data[self.front + i.start] as *const _ as *const T,
-            i.end - i.start,
-        );
+        let front = self.front.load(Ordering::Relaxed);
+        let start = Self::wrap_index(front.wrapping_add(i.start));
+        let len = i.end - i.start;
+        assert!(start + len <= N, "range wraps the physical buffer; use as_slices instead");
+        let d = core::ptr::slice_from_raw_parts(unsafe { self.slot(start) } as *const T, len);
         unsafe { &*d }
     }
 }
 
-impl<T: Copy> core::ops::IndexMut<core::ops::Range<usize>> for RingBuf<T> {
+impl<T, const N: usize> core::ops::IndexMut<core::ops::Range<usize>> for RingBuf<T, N> {
+    /// See the [`Index`] impl: panics instead of silently reading the wrong
+    /// slots when the range crosses the physical wrap point.
     fn index_mut(&mut self, i: core::ops::Range<usize>) -> &mut Self::Output {
         if self.len() < i.end {
             panic!("Out of bounds")
         }
-        let d = core::ptr::slice_from_raw_parts_mut(
-            &mut self.data[self.front + i.start] as *mut _ as *mut T,
-            i.end - i.start,
-        );
+        let front = self.front.load(Ordering::Relaxed);
+        let start = Self::wrap_index(front.wrapping_add(i.start));
+        let len = i.end - i.start;
+        assert!(start + len <= N, "range wraps the physical buffer; use as_slices instead");
+        let d = core::ptr::slice_from_raw_parts_mut(unsafe { self.slot(start) } as *mut T, len);
         unsafe { &mut *d }
     }
-}
\ No newline at end of file
+}
+
+/// A borrowing iterator over a [`RingBuf`], from `front` to `back`. See
+/// [`RingBuf::iter`].
+pub struct Iter<'a, T, const N: usize> {
+    buf: &'a RingBuf<T, N>,
+    idx: usize,
+}
+
+impl<'a, T, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let item = self.buf.get(self.idx)?;
+        self.idx += 1;
+        Some(item)
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a RingBuf<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A consuming iterator over a [`RingBuf`], from `front` to `back`.
+pub struct IntoIter<T, const N: usize> {
+    buf: RingBuf<T, N>,
+}
+
+impl<T, const N: usize> Iterator for IntoIter<T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.buf.pop_front().ok()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for RingBuf<T, N> {
+    type Item = T;
+    type IntoIter = IntoIter<T, N>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { buf: self }
+    }
+}
+
+impl<T, const N: usize> Extend<T> for RingBuf<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.push_from_iter(iter);
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for RingBuf<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut buf = Self::new();
+        buf.push_from_iter(iter);
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    extern crate std;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct DropCounter(Rc<Cell<usize>>);
+
+    impl Drop for DropCounter {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn push_overwriting_drops_evicted_element() {
+        let drops = Rc::new(Cell::new(0));
+        let mut buf: RingBuf<DropCounter, 2> = RingBuf::new();
+        buf.push_back(DropCounter(drops.clone())).unwrap();
+        buf.push_back(DropCounter(drops.clone())).unwrap();
+        assert_eq!(drops.get(), 0);
+
+        buf.push_back_overwriting(DropCounter(drops.clone()));
+        assert_eq!(drops.get(), 1, "oldest element must be dropped to make room");
+
+        buf.push_front_overwriting(DropCounter(drops.clone()));
+        assert_eq!(drops.get(), 2, "newest element must be dropped to make room");
+
+        drop(buf);
+        assert_eq!(drops.get(), 4, "remaining elements must be dropped with the buffer");
+    }
+
+    #[test]
+    fn into_iter_drops_remaining_elements_on_partial_consumption() {
+        let drops = Rc::new(Cell::new(0));
+        let mut buf: RingBuf<DropCounter, 4> = RingBuf::new();
+        for _ in 0..3 {
+            buf.push_back(DropCounter(drops.clone())).unwrap();
+        }
+
+        let mut iter = buf.into_iter();
+        assert!(iter.next().is_some());
+        assert_eq!(drops.get(), 1);
+
+        drop(iter);
+        assert_eq!(drops.get(), 3, "un-consumed elements must still be dropped");
+    }
+
+    #[test]
+    fn drop_cleans_up_unpopped_elements() {
+        let drops = Rc::new(Cell::new(0));
+        {
+            let mut buf: RingBuf<DropCounter, 4> = RingBuf::new();
+            buf.push_back(DropCounter(drops.clone())).unwrap();
+            buf.push_back(DropCounter(drops.clone())).unwrap();
+            buf.pop_front().unwrap();
+            assert_eq!(drops.get(), 1);
+        }
+        assert_eq!(drops.get(), 2, "buffer drop must clean up remaining elements");
+    }
+
+    #[test]
+    fn as_slices_and_make_contiguous_handle_physical_wraparound() {
+        let mut buf: RingBuf<i32, 4> = RingBuf::new();
+        buf.push_back(0).unwrap();
+        buf.push_back(1).unwrap();
+        buf.push_back(2).unwrap();
+        buf.push_back(3).unwrap();
+        buf.pop_front().unwrap();
+        buf.push_back(4).unwrap();
+
+        // front is now at physical index 1, so the logical contents [1, 2, 3, 4]
+        // are split across the physical end of the array.
+        let (first, second) = buf.as_slices();
+        assert_eq!(first, &[1, 2, 3]);
+        assert_eq!(second, &[4]);
+
+        // IndexMut must write through to the real backing slot, not a temporary.
+        buf[3] = 40;
+        assert_eq!(buf.as_slices(), (&[1, 2, 3][..], &[40][..]));
+
+        assert_eq!(buf.make_contiguous(), &[1, 2, 3, 40]);
+        // make_contiguous rotated the physical storage, so a fresh as_slices()
+        // call now returns everything in a single slice.
+        assert_eq!(buf.as_slices(), (&[1, 2, 3, 40][..], &[][..]));
+    }
+
+    #[test]
+    fn iterator_extend_and_from_iterator_work() {
+        let mut buf: RingBuf<i32, 4> = RingBuf::new();
+        buf.push_back(1).unwrap();
+        buf.push_back(2).unwrap();
+        buf.push_back(3).unwrap();
+
+        {
+            let mut iter = buf.iter();
+            assert_eq!(iter.next(), Some(&1));
+            assert_eq!(iter.next(), Some(&2));
+            assert_eq!(iter.next(), Some(&3));
+            assert_eq!(iter.next(), None);
+        }
+
+        {
+            let mut iter = (&buf).into_iter();
+            assert_eq!(iter.next(), Some(&1));
+        }
+
+        buf.extend([4]);
+        assert_eq!(buf.get(3), Some(&4));
+
+        let from_iter: RingBuf<i32, 4> = [10, 20, 30].into_iter().collect();
+        assert_eq!(from_iter.len(), 3);
+        assert_eq!(from_iter.get(0), Some(&10));
+        assert_eq!(from_iter.get(2), Some(&30));
+
+        let mut sum = 0;
+        for v in buf {
+            sum += v;
+        }
+        assert_eq!(sum, 1 + 2 + 3 + 4);
+    }
+
+    #[test]
+    fn overwriting_push_evicts_only_once_full() {
+        let mut buf: RingBuf<i32, 3> = RingBuf::new();
+        buf.push_back_overwriting(1);
+        buf.push_back_overwriting(2);
+        buf.push_back_overwriting(3);
+        assert_eq!(buf.len(), 3);
+        assert_eq!(buf.get(0), Some(&1), "not full yet, nothing should be evicted");
+
+        // Buffer is now full; this evicts the oldest (front) element.
+        buf.push_back_overwriting(4);
+        assert_eq!(buf.len(), 3);
+        assert_eq!(buf.get(0), Some(&2));
+        assert_eq!(buf.get(1), Some(&3));
+        assert_eq!(buf.get(2), Some(&4));
+
+        // Still full; push_front_overwriting evicts the newest (back) element instead.
+        buf.push_front_overwriting(0);
+        assert_eq!(buf.len(), 3);
+        assert_eq!(buf.get(0), Some(&0));
+        assert_eq!(buf.get(1), Some(&2));
+        assert_eq!(buf.get(2), Some(&3));
+    }
+
+    #[test]
+    fn wrap_index_works_for_power_of_two_and_non_power_of_two_capacity() {
+        let mut pow2: RingBuf<i32, 4> = RingBuf::new();
+        for i in 0..6 {
+            pow2.push_back_overwriting(i);
+        }
+        assert_eq!(pow2.len(), 4);
+        for (i, v) in (2..6).enumerate() {
+            assert_eq!(pow2.get(i), Some(&v));
+        }
+
+        let mut non_pow2: RingBuf<i32, 3> = RingBuf::new();
+        for i in 0..5 {
+            non_pow2.push_back_overwriting(i);
+        }
+        assert_eq!(non_pow2.len(), 3);
+        for (i, v) in (2..5).enumerate() {
+            assert_eq!(non_pow2.get(i), Some(&v));
+        }
+    }
+
+    #[test]
+    fn push_front_does_not_overflow_seq_front() {
+        let mut buf: RingBuf<u8, 4> = RingBuf::new();
+        buf.push_front(5).unwrap();
+        assert_eq!(buf.oldest_seq(), Some(u64::MAX));
+        assert_eq!(buf.newest_seq(), Some(u64::MAX));
+        assert_eq!(buf.get_abs(u64::MAX), Some(&5));
+    }
+
+    #[test]
+    fn pop_back_does_not_overflow_seq_back_on_empty_newest_seq() {
+        let mut buf: RingBuf<u8, 4> = RingBuf::new();
+        buf.push_front(1).unwrap();
+        buf.pop_back().unwrap();
+        assert_eq!(buf.oldest_seq(), None);
+        assert_eq!(buf.newest_seq(), None);
+    }
+
+    #[test]
+    fn split_keeps_sequence_numbers_in_sync() {
+        static mut BUF: RingBuf<u8, 4> = RingBuf::new();
+        // SAFETY: sole access to `BUF` in this test. `buf_ref`'s calls below are
+        // all from this single thread, strictly interleaved with `producer`/
+        // `consumer` (never concurrent with them), so this doesn't hit the
+        // `Relaxed`-read hazard documented on `split`.
+        let buf_ref: &'static RingBuf<u8, 4> = unsafe { &*core::ptr::addr_of!(BUF) };
+        let (mut producer, mut consumer) = unsafe { (*core::ptr::addr_of_mut!(BUF)).split() };
+
+        producer.push(1).unwrap();
+        producer.push(2).unwrap();
+        assert_eq!(buf_ref.oldest_seq(), Some(0));
+        assert_eq!(buf_ref.newest_seq(), Some(1));
+        assert_eq!(buf_ref.get_abs(0), Some(&1));
+        assert_eq!(buf_ref.get_abs(1), Some(&2));
+
+        assert_eq!(consumer.pull().unwrap(), 1);
+        assert_eq!(buf_ref.oldest_seq(), Some(1));
+        assert_eq!(buf_ref.newest_seq(), Some(1));
+        assert_eq!(buf_ref.get_abs(0), None);
+        assert_eq!(buf_ref.get_abs(1), Some(&2));
+    }
+}